@@ -4,7 +4,10 @@ use std::{
     fmt,
     hash::{Hash, Hasher},
     iter::{Skip, Take},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
     ops::{Bound, Deref, DerefMut, RangeBounds},
+    ptr,
 };
 
 macro_rules! cowslice {
@@ -19,59 +22,381 @@ macro_rules! cowslice {
 pub(crate) use cowslice;
 use ecow::EcoVec;
 
-pub struct CowSlice<T> {
-    data: EcoVec<T>,
-    start: usize,
-    end: usize,
+/// The number of bytes a [`CowSlice`] can hold in its [`Inline`](CowSlice::Inline)
+/// variant before it has to fall back to a heap-allocated [`EcoVec`].
+///
+/// Uiua creates huge numbers of short-lived, tiny arrays (shapes, rank-0
+/// scalars, small index vectors), and paying for a heap allocation on every
+/// one of them is wasteful. `CowSlice` borrows the small-value inlining
+/// trick from sled's `IVec`: instead of always pointing at heap data, it can
+/// stash a handful of elements directly in the struct. `24` is sized so the
+/// buffer is about the same width as the `Remote` variant's `EcoVec`
+/// pointer plus its two `usize` bounds; how many elements of a given `T`
+/// actually fit in it is computed by [`inline_cap`].
+const INLINE_BYTES: usize = 24;
+
+/// Alignment of the inline byte buffer. Types whose alignment exceeds this
+/// never get an inline representation; see [`inline_cap`].
+const INLINE_ALIGN: usize = 8;
+
+/// A fixed, 8-byte-aligned byte buffer used to store elements of a
+/// [`CowSlice`] inline, reinterpreted as `[MaybeUninit<T>]` by
+/// [`inline_slots`]/[`inline_slots_mut`].
+#[derive(Clone, Copy)]
+#[repr(align(8))]
+pub struct InlineBuf([MaybeUninit<u8>; INLINE_BYTES]);
+
+fn empty_inline_buf() -> InlineBuf {
+    InlineBuf([MaybeUninit::uninit(); INLINE_BYTES])
+}
+
+/// The number of `T` elements that fit in an [`InlineBuf`], or `0` if `T` is
+/// zero-sized or more strictly aligned than [`INLINE_ALIGN`] (in which case
+/// `CowSlice<T>` never uses the `Inline` representation at all).
+const fn inline_cap<T>() -> usize {
+    let size = mem::size_of::<T>();
+    if size == 0 || mem::align_of::<T>() > INLINE_ALIGN {
+        0
+    } else {
+        INLINE_BYTES / size
+    }
+}
+
+/// Reinterprets `buf` as a slice of `T` slots. Only the first
+/// [`inline_cap::<T>()`](inline_cap) of them are ever written to or read
+/// from by a `CowSlice<T>`.
+fn inline_slots<T>(buf: &InlineBuf) -> &[MaybeUninit<T>] {
+    // Safety: `InlineBuf` is 8-byte aligned, and `inline_cap::<T>()` is
+    // chosen so that this many `MaybeUninit<T>` slots fit within it with
+    // correct alignment (or is `0` for zero-sized/over-aligned `T`, in
+    // which case the pointer is never dereferenced).
+    unsafe {
+        std::slice::from_raw_parts(buf.0.as_ptr() as *const MaybeUninit<T>, inline_cap::<T>())
+    }
+}
+
+/// Mutable counterpart of [`inline_slots`].
+fn inline_slots_mut<T>(buf: &mut InlineBuf) -> &mut [MaybeUninit<T>] {
+    // Safety: see `inline_slots`.
+    unsafe {
+        std::slice::from_raw_parts_mut(buf.0.as_mut_ptr() as *mut MaybeUninit<T>, inline_cap::<T>())
+    }
+}
+
+/// Clones elements of `src` into the front of `buf`, stopping once either
+/// runs out.
+fn fill_uninit<'a, T: Clone + 'a>(
+    buf: &mut [MaybeUninit<T>],
+    src: impl IntoIterator<Item = &'a T>,
+) {
+    for (slot, item) in buf.iter_mut().zip(src) {
+        *slot = MaybeUninit::new(item.clone());
+    }
+}
+
+/// Like [`fill_uninit`], but clones out of another inline buffer's already-
+/// initialized slots rather than out of plain `T` references.
+fn clone_inline_into_uninit<T: Clone>(buf: &mut [MaybeUninit<T>], src: &[MaybeUninit<T>]) {
+    // Safety: every slot in `src` passed in by callers here is part of the
+    // initialized prefix of an `Inline` buffer.
+    fill_uninit(buf, src.iter().map(|slot| unsafe { &*slot.as_ptr() }));
+}
+
+/// Builds a `CowSlice` from `vec`, storing it inline instead of allocating
+/// when it's short enough to fit (see [`inline_cap`]).
+fn cow_from_vec<T: Clone>(vec: Vec<T>) -> CowSlice<T> {
+    let cap = inline_cap::<T>();
+    if cap > 0 && vec.len() <= cap {
+        let mut buf = empty_inline_buf();
+        fill_uninit(inline_slots_mut(&mut buf), vec.iter());
+        CowSlice::Inline {
+            len: vec.len() as u8,
+            buf,
+            marker: PhantomData,
+        }
+    } else {
+        let end = vec.len();
+        CowSlice::Remote {
+            data: vec.into(),
+            start: 0,
+            end,
+        }
+    }
+}
+
+/// Builds a `CowSlice` from `elems`, storing it inline instead of
+/// allocating when it's short enough to fit (see [`inline_cap`]).
+fn cow_from_slice<T: Clone>(elems: &[T]) -> CowSlice<T> {
+    let cap = inline_cap::<T>();
+    if cap > 0 && elems.len() <= cap {
+        let mut buf = empty_inline_buf();
+        fill_uninit(inline_slots_mut(&mut buf), elems);
+        CowSlice::Inline {
+            len: elems.len() as u8,
+            buf,
+            marker: PhantomData,
+        }
+    } else {
+        CowSlice::Remote {
+            data: elems.into(),
+            start: 0,
+            end: elems.len(),
+        }
+    }
+}
+
+pub enum CowSlice<T> {
+    /// A handful of elements stored directly in the struct, with no heap
+    /// allocation. How many elements fit depends on `size_of::<T>()`; see
+    /// [`inline_cap`]. Types that don't fit (including zero-sized and
+    /// over-aligned ones) always live in [`Remote`](CowSlice::Remote).
+    ///
+    /// This holds any `T: Clone`, not just `T: Copy`: the `From`/`FromIterator`
+    /// impls below are generic over `Clone` and need to route through here
+    /// uniformly, without a separate code path for `Copy` types. That means
+    /// non-`Copy` elements (e.g. `Rc`) can end up living here, so every path
+    /// that drops or removes inline elements (see `Drop` and `truncate`
+    /// below) has to clean up the initialized prefix itself, the same way an
+    /// `EcoVec` would.
+    Inline {
+        len: u8,
+        buf: InlineBuf,
+        marker: PhantomData<T>,
+    },
+    /// The original representation: a ref-counted, copy-on-write window
+    /// into a heap-allocated [`EcoVec`].
+    Remote {
+        data: EcoVec<T>,
+        start: usize,
+        end: usize,
+    },
 }
 
 impl<T> CowSlice<T> {
     pub fn new() -> Self {
         Self::default()
     }
+    /// Shrinks the slice to `len` elements. Panics if `len` is greater than
+    /// the slice's current length (this only ever shrinks, like
+    /// `Vec::truncate`).
     pub fn truncate(&mut self, len: usize) {
-        let end = self.start + len;
-        assert!(end <= self.data.len());
-        self.end = end;
+        match self {
+            CowSlice::Inline { len: l, buf, .. } => {
+                assert!(len <= *l as usize);
+                // The elements being dropped here are owned solely by this
+                // buffer (unlike `Remote`, where the `EcoVec` still owns
+                // them), so they need the same cleanup as `Drop` below.
+                drop_inline_range::<T>(buf, len, *l as usize);
+                *l = len as u8;
+            }
+            CowSlice::Remote { start, end, .. } => {
+                assert!(len <= *end - *start);
+                *end = *start + len;
+            }
+        }
+    }
+}
+
+/// Drops slots `[from, to)` of an inline buffer holding `T`. A no-op for
+/// types without drop glue (the common case: `f64`, `u8`, `char`, ...).
+fn drop_inline_range<T>(buf: &mut InlineBuf, from: usize, to: usize) {
+    if mem::needs_drop::<T>() {
+        for slot in &mut inline_slots_mut::<T>(buf)[from..to] {
+            // Safety: slots in `[from, to)` are part of the initialized
+            // prefix of an `Inline` buffer, and this is called at most once
+            // per slot before it's either overwritten or the buffer is
+            // dropped.
+            unsafe { slot.as_mut_ptr().drop_in_place() };
+        }
+    }
+}
+
+/// Drops the initialized prefix of an `Inline` buffer.
+impl<T> Drop for CowSlice<T> {
+    fn drop(&mut self) {
+        if let CowSlice::Inline { len, buf, .. } = self {
+            drop_inline_range::<T>(buf, 0, *len as usize);
+        }
     }
 }
 
 impl<T: Clone> CowSlice<T> {
+    /// Builds a `CowSlice` from `elems`, storing it inline instead of
+    /// allocating when it's short enough to fit.
+    pub fn from_slice(elems: &[T]) -> Self {
+        cow_from_slice(elems)
+    }
+
     pub fn slice<R>(&self, range: R) -> Self
     where
         R: RangeBounds<usize>,
     {
+        let len = self.len();
         let start = match range.start_bound() {
-            Bound::Included(&start) => self.start + start,
-            Bound::Excluded(&start) => self.start + start + 1,
-            Bound::Unbounded => self.start,
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
         };
         let end = match range.end_bound() {
-            Bound::Included(&end) => self.start + end + 1,
-            Bound::Excluded(&end) => self.start + end,
-            Bound::Unbounded => self.end,
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
         };
         assert!(start <= end);
-        assert!(end <= self.end);
-        Self {
-            data: self.data.clone(),
-            start,
-            end,
+        assert!(end <= len);
+        match self {
+            CowSlice::Inline { buf, .. } => {
+                // Small data: cheap to copy into a fresh inline buffer
+                // rather than bother with ref-counting.
+                let mut new_buf = empty_inline_buf();
+                clone_inline_into_uninit(
+                    inline_slots_mut(&mut new_buf),
+                    &inline_slots::<T>(buf)[start..end],
+                );
+                CowSlice::Inline {
+                    len: (end - start) as u8,
+                    buf: new_buf,
+                    marker: PhantomData,
+                }
+            }
+            CowSlice::Remote {
+                data,
+                start: self_start,
+                ..
+            } => CowSlice::Remote {
+                data: data.clone(),
+                start: self_start + start,
+                end: self_start + end,
+            },
         }
     }
+
+    /// Splits the slice into two views at `mid`. `Remote` halves share the
+    /// same backing [`EcoVec`]; `Inline` halves are copied into fresh inline
+    /// buffers (see [`slice`](Self::slice)). Panics if `mid > self.len()`.
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len());
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Splits off the first element, returning it alongside a view of the
+    /// rest, or `None` if the slice is empty.
+    pub fn split_first(&self) -> Option<(&T, Self)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some((&self[0], self.slice(1..)))
+        }
+    }
+
+    /// Splits off the last element, returning it alongside a view of the
+    /// rest, or `None` if the slice is empty.
+    pub fn split_last(&self) -> Option<(&T, Self)> {
+        if self.is_empty() {
+            None
+        } else {
+            let last = self.len() - 1;
+            Some((&self[last], self.slice(..last)))
+        }
+    }
+
+    /// Returns an iterator over `n`-element views of the slice, in order
+    /// from the start. The last chunk may be shorter than `n`. Panics if
+    /// `n == 0`.
+    pub fn chunks(&self, n: usize) -> CowSliceChunks<T> {
+        assert_ne!(n, 0, "chunk size must be non-zero");
+        CowSliceChunks {
+            rest: self.clone(),
+            size: n,
+        }
+    }
+
+    /// Returns an iterator over `n`-element views of the slice, in order
+    /// from the end. The last chunk (the remainder from the start) may be
+    /// shorter than `n`. Panics if `n == 0`.
+    pub fn rchunks(&self, n: usize) -> CowSliceRChunks<T> {
+        assert_ne!(n, 0, "chunk size must be non-zero");
+        CowSliceRChunks {
+            rest: self.clone(),
+            size: n,
+        }
+    }
+
+    /// Returns an iterator over all contiguous `n`-element views of the
+    /// slice. Panics if `n == 0`.
+    pub fn windows(&self, n: usize) -> CowSliceWindows<T> {
+        assert_ne!(n, 0, "window size must be non-zero");
+        CowSliceWindows {
+            rest: self.clone(),
+            size: n,
+        }
+    }
+
     pub fn modify<F, R>(&mut self, f: F) -> R
     where
         F: FnOnce(&mut EcoVec<T>) -> R,
     {
-        if self.data.is_unique() && self.start == 0 && self.end == self.data.len() {
-            let res = f(&mut self.data);
-            self.end = self.data.len();
-            res
+        match self {
+            CowSlice::Inline { len, buf, .. } => {
+                let items: Vec<T> = inline_slots::<T>(buf)[..*len as usize]
+                    .iter()
+                    .map(|slot| unsafe { (*slot.as_ptr()).clone() })
+                    .collect();
+                let mut vec: EcoVec<T> = items.into();
+                let res = f(&mut vec);
+                *self = Self::from_vec_preferring_inline(vec);
+                res
+            }
+            CowSlice::Remote { data, start, end } => {
+                if data.is_unique() {
+                    // Uniquely owned: reuse the existing allocation instead
+                    // of falling through to the reallocating copy path below.
+                    // If the view is offset from the front (e.g. produced by
+                    // `slice(1..)` or `split_first`), shift it down to index
+                    // 0 first. A front-peeling loop still does O(n^2) total
+                    // element moves, but it no longer pays for a fresh
+                    // allocation on every iteration.
+                    let len = *end - *start;
+                    if *start > 0 {
+                        data.make_mut().rotate_left(*start);
+                    }
+                    data.truncate(len);
+                    *start = 0;
+                    *end = len;
+                    let res = f(data);
+                    *end = data.len();
+                    res
+                } else {
+                    let mut vec = EcoVec::from(&**self);
+                    let res = f(&mut vec);
+                    *self = vec.into();
+                    res
+                }
+            }
+        }
+    }
+
+    /// Repacks a grown/shrunk `EcoVec` back into `Inline` storage when it's
+    /// small enough, otherwise keeps it `Remote`. Used to re-settle a
+    /// `CowSlice` after [`modify`](Self::modify) runs on data that started
+    /// out inline.
+    fn from_vec_preferring_inline(vec: EcoVec<T>) -> Self {
+        let cap = inline_cap::<T>();
+        if cap > 0 && vec.len() <= cap {
+            let mut buf = empty_inline_buf();
+            fill_uninit(inline_slots_mut(&mut buf), vec.iter());
+            CowSlice::Inline {
+                len: vec.len() as u8,
+                buf,
+                marker: PhantomData,
+            }
         } else {
-            let mut vec = EcoVec::from(&**self);
-            let res = f(&mut vec);
-            *self = vec.into();
-            res
+            let len = vec.len();
+            CowSlice::Remote {
+                data: vec,
+                start: 0,
+                end: len,
+            }
         }
     }
 }
@@ -88,9 +413,221 @@ fn cow_slice_modify() {
     assert_eq!(sub, [2, 3, 5]);
 }
 
+/// Iterator over `n`-element views of a [`CowSlice`], yielded from the
+/// start. Returned by [`CowSlice::chunks`].
+pub struct CowSliceChunks<T> {
+    rest: CowSlice<T>,
+    size: usize,
+}
+
+impl<T: Clone> Iterator for CowSliceChunks<T> {
+    type Item = CowSlice<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let n = self.size.min(self.rest.len());
+        let (chunk, rest) = self.rest.split_at(n);
+        self.rest = rest;
+        Some(chunk)
+    }
+}
+
+/// Iterator over `n`-element views of a [`CowSlice`], yielded from the end.
+/// Returned by [`CowSlice::rchunks`].
+pub struct CowSliceRChunks<T> {
+    rest: CowSlice<T>,
+    size: usize,
+}
+
+impl<T: Clone> Iterator for CowSliceRChunks<T> {
+    type Item = CowSlice<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = self.rest.len();
+        let n = self.size.min(len);
+        let (rest, chunk) = self.rest.split_at(len - n);
+        self.rest = rest;
+        Some(chunk)
+    }
+}
+
+/// Iterator over all contiguous `n`-element views of a [`CowSlice`].
+/// Returned by [`CowSlice::windows`].
+pub struct CowSliceWindows<T> {
+    rest: CowSlice<T>,
+    size: usize,
+}
+
+impl<T: Clone> Iterator for CowSliceWindows<T> {
+    type Item = CowSlice<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size > self.rest.len() {
+            return None;
+        }
+        let window = self.rest.slice(..self.size);
+        self.rest = self.rest.slice(1..);
+        Some(window)
+    }
+}
+
+#[test]
+fn cow_slice_split_at() {
+    let slice = CowSlice::from([1, 2, 3, 4, 5]);
+    let (left, right) = slice.split_at(2);
+    assert_eq!(left, [1, 2]);
+    assert_eq!(right, [3, 4, 5]);
+
+    let (first, rest) = slice.split_first().unwrap();
+    assert_eq!(*first, 1);
+    assert_eq!(rest, [2, 3, 4, 5]);
+
+    let (last, rest) = slice.split_last().unwrap();
+    assert_eq!(*last, 5);
+    assert_eq!(rest, [1, 2, 3, 4]);
+
+    let empty: CowSlice<i32> = CowSlice::new();
+    assert!(empty.split_first().is_none());
+    assert!(empty.split_last().is_none());
+}
+
+#[test]
+fn cow_slice_chunks_windows() {
+    let slice = CowSlice::from([1, 2, 3, 4, 5]);
+
+    let chunks: Vec<CowSlice<i32>> = slice.chunks(2).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[0], [1, 2]);
+    assert_eq!(chunks[1], [3, 4]);
+    assert_eq!(chunks[2], [5]);
+
+    let rchunks: Vec<CowSlice<i32>> = slice.rchunks(2).collect();
+    assert_eq!(rchunks.len(), 3);
+    assert_eq!(rchunks[0], [4, 5]);
+    assert_eq!(rchunks[1], [2, 3]);
+    assert_eq!(rchunks[2], [1]);
+
+    let windows: Vec<CowSlice<i32>> = slice.windows(3).collect();
+    assert_eq!(windows.len(), 3);
+    assert_eq!(windows[0], [1, 2, 3]);
+    assert_eq!(windows[1], [2, 3, 4]);
+    assert_eq!(windows[2], [3, 4, 5]);
+
+    assert_eq!(slice.windows(10).count(), 0);
+}
+
+#[test]
+fn cow_slice_modify_front_peel_reuses_buffer() {
+    // Longer than `inline_cap::<i32>()`, so this is stored `Remote` and the
+    // loop below actually exercises the unique-offset-buffer reuse path in
+    // `modify`, rather than trivially stable stack storage.
+    let mut expected: Vec<i32> = (1..=100).collect();
+    let mut slice = CowSlice::from(expected.clone());
+    assert!(matches!(slice, CowSlice::Remote { .. }));
+    let base_ptr = slice.as_ptr();
+    while let Some((_, rest)) = slice.split_first() {
+        slice = rest;
+        expected.remove(0);
+        // A no-op modify; what matters is that it doesn't move the
+        // underlying allocation even though `slice` is offset from the
+        // front of it.
+        slice.modify(|_vec| {});
+        assert_eq!(slice, *expected);
+        assert_eq!(
+            slice.as_ptr(),
+            base_ptr,
+            "front-peeling a unique CowSlice should reuse its allocation in place"
+        );
+    }
+}
+
+#[test]
+fn cow_slice_inline_transparent() {
+    let inline = CowSlice::from_slice(&[1, 2, 3]);
+    assert_eq!(inline, [1, 2, 3]);
+
+    let sub = inline.slice(1..);
+    assert_eq!(sub, [2, 3]);
+
+    let mut grown = inline.clone();
+    grown.modify(|vec| vec.extend([4, 5, 6, 7]));
+    assert_eq!(grown, [1, 2, 3, 4, 5, 6, 7]);
+
+    let mut collected = CowSlice::new();
+    collected.extend(inline);
+    assert_eq!(collected, [1, 2, 3]);
+}
+
+#[test]
+fn cow_slice_constructors_use_inline_storage() {
+    assert!(matches!(CowSlice::from([1, 2, 3]), CowSlice::Inline { .. }));
+    assert!(matches!(
+        CowSlice::from(vec![1_i32, 2, 3]),
+        CowSlice::Inline { .. }
+    ));
+    assert!(matches!(
+        CowSlice::from([1, 2, 3].as_slice()),
+        CowSlice::Inline { .. }
+    ));
+    assert!(matches!(
+        CowSlice::from(vec![1_i32; 100]),
+        CowSlice::Remote { .. }
+    ));
+}
+
+#[test]
+fn cow_slice_inline_drops_non_copy_elements() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let slice = CowSlice::from(vec![counter.clone(), counter.clone()]);
+    assert!(matches!(slice, CowSlice::Inline { .. }));
+    assert_eq!(Rc::strong_count(&counter), 3);
+    drop(slice);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn cow_slice_inline_truncate_drops_removed_elements() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut slice = CowSlice::from(vec![counter.clone(), counter.clone(), counter.clone()]);
+    assert!(matches!(slice, CowSlice::Inline { .. }));
+    assert_eq!(Rc::strong_count(&counter), 4);
+
+    slice.truncate(1);
+    assert_eq!(
+        Rc::strong_count(&counter),
+        2,
+        "truncating inline storage must drop the elements it removes"
+    );
+    drop(slice);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+/// A type whose alignment exceeds `INLINE_ALIGN`, so `inline_cap` is always
+/// `0` for it and it must always be stored `Remote`, even when empty.
+#[derive(Clone)]
+#[repr(align(16))]
+struct OverAligned(u64);
+
+#[test]
+fn cow_slice_over_aligned_type_never_inline() {
+    let empty: CowSlice<OverAligned> = CowSlice::from(Vec::new());
+    assert!(matches!(empty, CowSlice::Remote { .. }));
+    assert_eq!(empty.len(), 0);
+
+    let one = CowSlice::from(vec![OverAligned(5)]);
+    assert!(matches!(one, CowSlice::Remote { .. }));
+    assert_eq!(one[0].0, 5);
+}
+
 impl<T> Default for CowSlice<T> {
     fn default() -> Self {
-        Self {
+        CowSlice::Remote {
             data: EcoVec::new(),
             start: 0,
             end: 0,
@@ -100,10 +637,24 @@ impl<T> Default for CowSlice<T> {
 
 impl<T: Clone> Clone for CowSlice<T> {
     fn clone(&self) -> Self {
-        Self {
-            data: self.data.clone(),
-            start: self.start,
-            end: self.end,
+        match self {
+            CowSlice::Inline { len, buf, .. } => {
+                let mut new_buf = empty_inline_buf();
+                clone_inline_into_uninit(
+                    inline_slots_mut(&mut new_buf),
+                    &inline_slots::<T>(buf)[..*len as usize],
+                );
+                CowSlice::Inline {
+                    len: *len,
+                    buf: new_buf,
+                    marker: PhantomData,
+                }
+            }
+            CowSlice::Remote { data, start, end } => CowSlice::Remote {
+                data: data.clone(),
+                start: *start,
+                end: *end,
+            },
         }
     }
 }
@@ -111,16 +662,35 @@ impl<T: Clone> Clone for CowSlice<T> {
 impl<T> Deref for CowSlice<T> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
-        &self.data[self.start..self.end]
+        match self {
+            CowSlice::Inline { len, buf, .. } => unsafe {
+                std::slice::from_raw_parts(buf.0.as_ptr() as *const T, *len as usize)
+            },
+            CowSlice::Remote { data, start, end } => &data[*start..*end],
+        }
     }
 }
 
 impl<T: Clone> DerefMut for CowSlice<T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        if !self.data.is_unique() {
-            *self = self.to_vec().into();
+        match self {
+            CowSlice::Remote { data, start, end } => {
+                if !data.is_unique() {
+                    let len = *end - *start;
+                    let owned: EcoVec<T> = data[*start..*end].to_vec().into();
+                    *data = owned;
+                    *start = 0;
+                    *end = len;
+                }
+            }
+            CowSlice::Inline { .. } => {}
+        }
+        match self {
+            CowSlice::Inline { len, buf, .. } => unsafe {
+                std::slice::from_raw_parts_mut(buf.0.as_mut_ptr() as *mut T, *len as usize)
+            },
+            CowSlice::Remote { data, start, end } => &mut data.make_mut()[*start..*end],
         }
-        self.data.make_mut()
     }
 }
 
@@ -138,17 +708,13 @@ fn cow_slice_deref_mut() {
 
 impl<T: Clone> From<Vec<T>> for CowSlice<T> {
     fn from(vec: Vec<T>) -> Self {
-        Self {
-            start: 0,
-            end: vec.len(),
-            data: vec.into(),
-        }
+        cow_from_vec(vec)
     }
 }
 
 impl<T: Clone> From<EcoVec<T>> for CowSlice<T> {
     fn from(data: EcoVec<T>) -> Self {
-        Self {
+        CowSlice::Remote {
             start: 0,
             end: data.len(),
             data,
@@ -158,21 +724,13 @@ impl<T: Clone> From<EcoVec<T>> for CowSlice<T> {
 
 impl<'a, T: Clone> From<&'a [T]> for CowSlice<T> {
     fn from(slice: &'a [T]) -> Self {
-        Self {
-            start: 0,
-            end: slice.len(),
-            data: slice.into(),
-        }
+        cow_from_slice(slice)
     }
 }
 
 impl<T: Clone, const N: usize> From<[T; N]> for CowSlice<T> {
     fn from(array: [T; N]) -> Self {
-        Self {
-            start: 0,
-            end: N,
-            data: array.into(),
-        }
+        cow_from_vec(array.into())
     }
 }
 
@@ -238,15 +796,55 @@ impl<T: Hash> Hash for CowSlice<T> {
     }
 }
 
+/// The owned iterator for [`CowSlice`], yielding elements out of whichever
+/// variant the slice happens to be in.
+pub enum CowSliceIntoIter<T: Clone> {
+    Inline(std::vec::IntoIter<T>),
+    Remote(Take<Skip<<EcoVec<T> as IntoIterator>::IntoIter>>),
+}
+
+impl<T: Clone> Iterator for CowSliceIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        match self {
+            CowSliceIntoIter::Inline(it) => it.next(),
+            CowSliceIntoIter::Remote(it) => it.next(),
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            CowSliceIntoIter::Inline(it) => it.size_hint(),
+            CowSliceIntoIter::Remote(it) => it.size_hint(),
+        }
+    }
+}
+
 impl<T: Clone> IntoIterator for CowSlice<T> {
     type Item = T;
-    type IntoIter = Take<Skip<<EcoVec<T> as IntoIterator>::IntoIter>>;
-    #[allow(clippy::unnecessary_to_owned)]
+    type IntoIter = CowSliceIntoIter<T>;
     fn into_iter(self) -> Self::IntoIter {
-        self.data
-            .into_iter()
-            .skip(self.start)
-            .take(self.end - self.start)
+        // `CowSlice` has a `Drop` impl, so its fields can't be moved out of
+        // a by-value `self` directly. Suppress that destructor and pull the
+        // fields we need out through raw pointers/borrows instead.
+        let mut this = ManuallyDrop::new(self);
+        match &mut *this {
+            CowSlice::Inline { len, buf, .. } => {
+                let mut items = Vec::with_capacity(*len as usize);
+                for slot in &inline_slots::<T>(buf)[..*len as usize] {
+                    // Safety: the first `len` slots are initialized, and we
+                    // only read each one once.
+                    items.push(unsafe { slot.as_ptr().read() });
+                }
+                CowSliceIntoIter::Inline(items.into_iter())
+            }
+            CowSlice::Remote { data, start, end } => {
+                // Safety: `this` is never used again after this read, and
+                // its destructor is suppressed by `ManuallyDrop`, so `data`
+                // is read out of it exactly once.
+                let data = unsafe { ptr::read(data) };
+                CowSliceIntoIter::Remote(data.into_iter().skip(*start).take(*end - *start))
+            }
+        }
     }
 }
 
@@ -276,4 +874,4 @@ impl<T: Clone> Extend<T> for CowSlice<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         self.modify(|vec| vec.extend(iter))
     }
-}
\ No newline at end of file
+}